@@ -0,0 +1,111 @@
+//! The "peaceful chess queen armies" problem.
+//!
+//! Place `m` white queens and `m` black queens on an `n` x `n` board so that no
+//! queen attacks any queen of the *opposite* color, where attack here is the
+//! full chess-queen line — the entire row, the entire column, and both full
+//! diagonals — rather than this crate's one-off adjacency rule. Queens of the
+//! same color may freely share lines; only cross-color attacks are forbidden.
+//!
+//! Each army is represented as its own [`QueenBoard`]. The attack relation is
+//! symmetric, so it is enough to ensure that no queen is ever placed on a cell
+//! that lies on an opposing queen's [`QueenBoard::attacks_full`] mask.
+
+use crate::QueenBoard;
+
+/// Try to place a peaceful pair of armies, scanning cells in linear order and,
+/// for each, leaving it empty or assigning it to one army.
+fn place(
+    idx: usize,
+    n: usize,
+    white: QueenBoard,
+    black: QueenBoard,
+    white_left: usize,
+    black_left: usize,
+) -> Option<(QueenBoard, QueenBoard)> {
+    if white_left == 0 && black_left == 0 {
+        return Some((white, black));
+    }
+
+    let total_cells = n * n;
+
+    // Not enough cells remain to seat the queens still owed to each army.
+    if total_cells - idx < white_left + black_left {
+        return None;
+    }
+
+    let rays = white.attacks_full(idx); // same geometry regardless of color
+
+    // Option 1: place a white queen here, if no black queen attacks the cell.
+    if white_left > 0 && !rays.intersects(&black) {
+        let mut white = white;
+        white.set_linear_index(idx, true);
+        if let Some(result) = place(idx + 1, n, white, black, white_left - 1, black_left) {
+            return Some(result);
+        }
+    }
+
+    // Option 2: place a black queen here, if no white queen attacks the cell.
+    if black_left > 0 && !rays.intersects(&white) {
+        let mut black = black;
+        black.set_linear_index(idx, true);
+        if let Some(result) = place(idx + 1, n, white, black, white_left, black_left - 1) {
+            return Some(result);
+        }
+    }
+
+    // Option 3: leave this cell empty.
+    place(idx + 1, n, white, black, white_left, black_left)
+}
+
+/// Solve the peaceful armies problem for `m` queens per color on an `n` x `n`
+/// board, returning the white and black boards when a placement exists.
+#[must_use]
+pub fn solve_peaceful_armies(n: usize, m: usize) -> Option<(QueenBoard, QueenBoard)> {
+    let white = QueenBoard::new(n, n);
+    let black = QueenBoard::new(n, n);
+    place(0, n, white, black, m, m)
+}
+
+/// Return the largest `m` for which two peaceful armies of `m` queens each fit
+/// on an `n` x `n` board.
+///
+/// This is brute force: each candidate `m` drives [`solve_peaceful_armies`],
+/// which must exhaust a cell-assignment tree of up to `3^(n*n)` branches (every
+/// cell left empty or given to one army; attacked cells and branches with too
+/// few cells left are pruned) before it can prove an `m` unsolvable. Fine for
+/// the small boards exercised here, but it does not scale to large `n`.
+#[must_use]
+pub fn max_peaceful_m(n: usize) -> usize {
+    let mut best = 0;
+    // Each army can occupy at most half the board.
+    for m in 1..=(n * n / 2) {
+        if solve_peaceful_armies(n, m).is_some() {
+            best = m;
+        } else {
+            break;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trivial_zero_armies() {
+        assert!(solve_peaceful_armies(4, 0).is_some());
+    }
+
+    #[test]
+    fn test_no_solution_when_too_crowded() {
+        // Two queens per color cannot coexist peacefully on a 3x3 board.
+        assert!(solve_peaceful_armies(3, 2).is_none());
+    }
+
+    #[test]
+    fn test_max_peaceful_m_small_boards() {
+        assert_eq!(max_peaceful_m(3), 1);
+        assert_eq!(max_peaceful_m(4), 2);
+    }
+}
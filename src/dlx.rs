@@ -0,0 +1,325 @@
+//! Exact-cover solving backend using Algorithm X with Dancing Links.
+//!
+//! The Queens puzzle is modeled as an exact-cover problem: the rows of a sparse
+//! 0/1 matrix are candidate placements (one per cell that belongs to some color
+//! region) and the columns are the "exactly one" constraints — one column per
+//! color region, one per board row, and one per board column. A solution covers
+//! every region, every row, and every column exactly once.
+//!
+//! The one-off-diagonal rule ("no two queens touching") is not expressible as an
+//! exact-cover column, so it is checked as a cheap guard when a candidate row is
+//! selected: the row is rejected if any already-chosen queen sits in an adjacent
+//! diagonal cell.
+
+use crate::{QueenBoard, parse_color_region_inds};
+
+/// A toroidal doubly-linked list of nodes implementing Knuth's Dancing Links.
+///
+/// Node `0` is the root header; nodes `1..=num_columns` are the column headers,
+/// each carrying a `size` count; the remaining nodes are the matrix's ones.
+struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// Column header index each node belongs to (itself, for a header).
+    col: Vec<usize>,
+    /// Number of ones currently in a column (meaningful for headers).
+    size: Vec<usize>,
+    /// The placement row a data node belongs to (meaningless for headers).
+    node_row: Vec<usize>,
+    /// Linear cell index of each placement row, for board reconstruction and the
+    /// diagonal guard.
+    row_cell: Vec<usize>,
+    n_rows: usize,
+    n_cols: usize,
+}
+
+impl Dlx {
+    /// Create the linked matrix with `num_columns` empty column headers.
+    fn new(num_columns: usize, n_rows: usize, n_cols: usize) -> Self {
+        let n = num_columns + 1;
+        let mut dlx = Dlx {
+            left: (0..n).collect(),
+            right: (0..n).collect(),
+            up: (0..n).collect(),
+            down: (0..n).collect(),
+            col: (0..n).collect(),
+            size: vec![0; n],
+            node_row: vec![0; n],
+            row_cell: Vec::new(),
+            n_rows,
+            n_cols,
+        };
+
+        // Link the root (0) and the column headers into one horizontal ring.
+        for c in 0..=num_columns {
+            dlx.left[c] = if c == 0 { num_columns } else { c - 1 };
+            dlx.right[c] = if c == num_columns { 0 } else { c + 1 };
+        }
+
+        dlx
+    }
+
+    /// Append a fresh node and return its index.
+    fn alloc(&mut self, col: usize, node_row: usize) -> usize {
+        let idx = self.left.len();
+        self.left.push(idx);
+        self.right.push(idx);
+        self.up.push(idx);
+        self.down.push(idx);
+        self.col.push(col);
+        self.size.push(0);
+        self.node_row.push(node_row);
+        idx
+    }
+
+    /// Add a matrix row covering the given `columns`, tagged with `cell` (its
+    /// board linear index).
+    fn add_row(&mut self, columns: &[usize], cell: usize) {
+        let row_id = self.row_cell.len();
+        self.row_cell.push(cell);
+
+        let mut first = None;
+        let mut prev = 0;
+        for &c in columns {
+            let node = self.alloc(c, row_id);
+
+            // Splice into column `c` just above its header.
+            let up = self.up[c];
+            self.down[up] = node;
+            self.up[node] = up;
+            self.down[node] = c;
+            self.up[c] = node;
+            self.size[c] += 1;
+
+            // Splice into the row's horizontal ring.
+            match first {
+                None => first = Some(node),
+                Some(f) => {
+                    self.right[prev] = node;
+                    self.left[node] = prev;
+                    self.left[f] = node;
+                    self.right[node] = f;
+                }
+            }
+            prev = node;
+        }
+    }
+
+    /// Remove column `c` and every row that intersects it from the matrix.
+    fn cover(&mut self, c: usize) {
+        self.right[self.left[c]] = self.right[c];
+        self.left[self.right[c]] = self.left[c];
+
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                self.down[self.up[j]] = self.down[j];
+                self.up[self.down[j]] = self.up[j];
+                let cj = self.col[j];
+                self.size[cj] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    /// Reverse [`Dlx::cover`], reinserting column `c` and its rows.
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                let cj = self.col[j];
+                self.size[cj] += 1;
+                self.down[self.up[j]] = j;
+                self.up[self.down[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+
+        self.right[self.left[c]] = c;
+        self.left[self.right[c]] = c;
+    }
+
+    /// Choose the uncovered column with the smallest size (Knuth's `S`
+    /// heuristic). Returns `None` when the matrix is empty, i.e. a cover is
+    /// complete.
+    fn choose_column(&self) -> Option<usize> {
+        let mut best = None;
+        let mut best_size = usize::MAX;
+        let mut c = self.right[0];
+        while c != 0 {
+            if self.size[c] < best_size {
+                best_size = self.size[c];
+                best = Some(c);
+            }
+            c = self.right[c];
+        }
+        best
+    }
+
+    /// Return `true` if a queen at `cell` would touch any queen in `chosen` on a
+    /// one-off diagonal.
+    fn diagonal_clash(&self, cell: usize, chosen: &[usize]) -> bool {
+        let row = cell / self.n_cols;
+        let col = cell % self.n_cols;
+        chosen.iter().any(|&other| {
+            let orow = other / self.n_cols;
+            let ocol = other % self.n_cols;
+            let dr = row.abs_diff(orow);
+            let dc = col.abs_diff(ocol);
+            dr == 1 && dc == 1
+        })
+    }
+
+    /// Algorithm X. Collects the board cells (linear indices) of the placement
+    /// rows of each cover into `chosen`;
+    /// returns `true` from the first complete cover unless `find_all` is set, in
+    /// which case every cover is enumerated and counted in `count`. `nodes`
+    /// accumulates the number of search nodes visited.
+    fn search(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        find_all: bool,
+        count: &mut usize,
+        nodes: &mut usize,
+    ) -> bool {
+        *nodes += 1;
+
+        let Some(c) = self.choose_column() else {
+            // Every constraint is satisfied: this is a complete cover.
+            *count += 1;
+            return !find_all;
+        };
+
+        // An unsatisfiable column means this branch is dead.
+        if self.size[c] == 0 {
+            return false;
+        }
+
+        self.cover(c);
+
+        let mut r = self.down[c];
+        while r != c {
+            let cell = self.row_cell[self.node_row[r]];
+            if !self.diagonal_clash(cell, chosen) {
+                chosen.push(cell);
+                let mut j = self.right[r];
+                while j != r {
+                    self.cover(self.col[j]);
+                    j = self.right[j];
+                }
+
+                if self.search(chosen, find_all, count, nodes) {
+                    return true;
+                }
+
+                let mut j = self.left[r];
+                while j != r {
+                    self.uncover(self.col[j]);
+                    j = self.left[j];
+                }
+                chosen.pop();
+            }
+            r = self.down[r];
+        }
+
+        self.uncover(c);
+        false
+    }
+}
+
+/// Build the Dancing Links matrix for a parsed puzzle.
+fn build(raw_color_regions: &str) -> Dlx {
+    let (regions, n_rows, n_cols) = parse_color_region_inds(raw_color_regions);
+
+    // Column layout: [regions..][board rows..][board cols..].
+    let n_regions = regions.len();
+    let region_base = 1; // column headers are 1-indexed
+    let row_base = region_base + n_regions;
+    let col_base = row_base + n_rows;
+    let num_columns = n_regions + n_rows + n_cols;
+
+    let mut dlx = Dlx::new(num_columns, n_rows, n_cols);
+
+    for (region_idx, inds) in regions.values().enumerate() {
+        for &cell in inds {
+            let row = cell / n_cols;
+            let col = cell % n_cols;
+            let columns = [
+                region_base + region_idx,
+                row_base + row,
+                col_base + col,
+            ];
+            dlx.add_row(&columns, cell);
+        }
+    }
+
+    dlx
+}
+
+/// Solve the Queens puzzle as an exact-cover problem, returning the first
+/// solution found (if any) and the number of search nodes visited.
+#[must_use]
+pub fn solve_exact_cover(raw_color_regions: &str) -> (Option<QueenBoard>, usize) {
+    let mut dlx = build(raw_color_regions);
+    let n_rows = dlx.n_rows;
+    let n_cols = dlx.n_cols;
+
+    let mut chosen = Vec::new();
+    let mut count = 0;
+    let mut nodes = 0;
+
+    if dlx.search(&mut chosen, false, &mut count, &mut nodes) {
+        let mut board = QueenBoard::new(n_rows, n_cols);
+        for &cell in &chosen {
+            board.set_linear_index(cell, true);
+        }
+        (Some(board), nodes)
+    } else {
+        (None, nodes)
+    }
+}
+
+/// Enumerate every exact cover and return how many distinct solutions exist.
+#[must_use]
+pub fn count_solutions(raw_color_regions: &str) -> usize {
+    let mut dlx = build(raw_color_regions);
+    let mut chosen = Vec::new();
+    let mut count = 0;
+    let mut nodes = 0;
+    dlx.search(&mut chosen, true, &mut count, &mut nodes);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_cover_regions_are_columns() {
+        let raw_color_regions = "12345 12345 12345 12345 12345 ";
+        let (board, _) = solve_exact_cover(raw_color_regions);
+        assert!(board.is_some());
+    }
+
+    #[test]
+    fn test_exact_cover_actual_board() {
+        let raw_color_regions =
+            "11112333 11222344 11255346 77253344 73355334 77335344 87355333 77333333";
+        let (board, _) = solve_exact_cover(raw_color_regions);
+        assert!(board.is_some());
+    }
+
+    #[test]
+    fn test_count_solutions_well_posed_board_is_unique() {
+        // A real LinkedIn Queens board has exactly one solution.
+        let raw_color_regions =
+            "11112333 11222344 11255346 77253344 73355334 77335344 87355333 77333333";
+        assert_eq!(count_solutions(raw_color_regions), 1);
+    }
+}
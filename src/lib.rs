@@ -4,6 +4,11 @@ use bit_board::bitboardstatic::BitBoardStatic;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+
+pub mod armies;
+pub mod dlx;
+pub mod logic;
 
 #[derive(Debug, PartialEq)]
 pub enum BoardPlacementResult {
@@ -119,6 +124,189 @@ impl QueenBoard {
     pub fn col_is_empty(&self, col: usize) -> bool {
         self.0.get_col(col).all(|item| !item)
     }
+
+    /// Count how many bits are set on the board.
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.get_linear_indices().count()
+    }
+
+    /// Return `true` if no bits are set on the board.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.get_linear_indices().next().is_none()
+    }
+
+    /// If exactly one bit is set, return its linear index (a "naked single");
+    /// otherwise return `None`.
+    #[must_use]
+    pub fn only_candidate(&self) -> Option<usize> {
+        let mut ones = self.get_linear_indices();
+        let first = ones.next()?;
+        if ones.next().is_none() {
+            Some(first)
+        } else {
+            None
+        }
+    }
+
+    /// Clear every cell that conflicts with a queen placed at `linear_idx`: the
+    /// whole row, the whole column, and the up-to-four one-off diagonal
+    /// neighbors (and the cell itself). Used by the constraint-propagation
+    /// solver to shrink the other regions' candidate masks after a placement.
+    pub fn eliminate_conflicts(&mut self, linear_idx: usize) {
+        let (row, col) = self.0.row_col_of(linear_idx);
+        let n_rows = self.0.n_rows();
+        let n_cols = self.0.n_cols();
+
+        // The whole row (also clears the cell itself).
+        for c in 0..n_cols {
+            self.set_linear_index(row * n_cols + c, false);
+        }
+
+        // The whole column.
+        for r in 0..n_rows {
+            self.set_linear_index(r * n_cols + col, false);
+        }
+
+        // The four one-off diagonal neighbors, when they exist.
+        if row > 0 && col > 0 {
+            self.set_linear_index((row - 1) * n_cols + (col - 1), false);
+        }
+        if row > 0 && col < n_cols - 1 {
+            self.set_linear_index((row - 1) * n_cols + (col + 1), false);
+        }
+        if row < n_rows - 1 && col > 0 {
+            self.set_linear_index((row + 1) * n_cols + (col - 1), false);
+        }
+        if row < n_rows - 1 && col < n_cols - 1 {
+            self.set_linear_index((row + 1) * n_cols + (col + 1), false);
+        }
+    }
+
+    /// Return `true` if the bit at the linear index `linear_idx` is set.
+    #[must_use]
+    pub fn is_set(&self, linear_idx: usize) -> bool {
+        let (row, col) = self.0.row_col_of(linear_idx);
+        self.0.get(row, col)
+    }
+
+    /// Return `true` if any bit set on this board is also set on `other`.
+    #[must_use]
+    pub fn intersects(&self, other: &QueenBoard) -> bool {
+        self.get_linear_indices().any(|idx| other.is_set(idx))
+    }
+
+    /// Build the full chess-queen attack mask for a queen at `idx`: the entire
+    /// row, the entire column, and both full diagonals. Unlike
+    /// [`QueenBoard::one_off_diagonals_are_empty`], which only looks one cell
+    /// away, these are complete rays spanning the board — the attack model used
+    /// by the peaceful-armies subsystem.
+    #[must_use]
+    pub fn attacks_full(&self, idx: usize) -> QueenBoard {
+        let (row, col) = self.0.row_col_of(idx);
+        let n_rows = self.0.n_rows();
+        let n_cols = self.0.n_cols();
+        let mut mask = QueenBoard::new(n_rows, n_cols);
+
+        for c in 0..n_cols {
+            mask.set_linear_index(row * n_cols + c, true);
+        }
+        for r in 0..n_rows {
+            mask.set_linear_index(r * n_cols + col, true);
+        }
+        for r in 0..n_rows {
+            for c in 0..n_cols {
+                if r.abs_diff(row) == c.abs_diff(col) {
+                    mask.set_linear_index(r * n_cols + c, true);
+                }
+            }
+        }
+
+        mask
+    }
+
+    /// Return `true` if a queen placed at `idx` would conflict with any queen
+    /// currently on this (occupied) board, using the precomputed attack table.
+    /// This collapses the separate row, column, and one-off-diagonal scans into
+    /// a single intersection against `attacks[idx]`.
+    #[must_use]
+    pub fn conflicts_with(&self, attacks: &QueenAttacks, idx: usize) -> bool {
+        self.intersects(attacks.mask(idx))
+    }
+}
+
+/// Precomputed per-cell attack masks for placement validation.
+///
+/// For a given board size, `QueenAttacks` stores, for every linear cell index, a
+/// single mask holding all the cells that conflict with a queen placed there:
+/// the entire row, the entire column, and the up-to-four one-off diagonal
+/// neighbors. This follows the magic-bitboard precomputation idea from chess
+/// engines — a placement is legal iff the occupied board does not intersect the
+/// cell's mask, turning three scans into one intersection test. That test
+/// ([`QueenBoard::intersects`]) walks the occupied board's set cells and looks
+/// each one up in the mask; it is not a single word-wise bitwise AND. Tables
+/// are built once per board size and cached in [`QueenAttacks::get`].
+#[derive(Debug)]
+pub struct QueenAttacks {
+    masks: Vec<QueenBoard>,
+}
+
+impl QueenAttacks {
+    /// Build the attack table for an `n_rows` x `n_cols` board.
+    #[must_use]
+    pub fn new(n_rows: usize, n_cols: usize) -> Self {
+        let mut masks = Vec::with_capacity(n_rows * n_cols);
+        for idx in 0..(n_rows * n_cols) {
+            let row = idx / n_cols;
+            let col = idx % n_cols;
+            let mut mask = QueenBoard::new(n_rows, n_cols);
+
+            // The entire row and column.
+            for c in 0..n_cols {
+                mask.set_linear_index(row * n_cols + c, true);
+            }
+            for r in 0..n_rows {
+                mask.set_linear_index(r * n_cols + col, true);
+            }
+
+            // The four one-off diagonal neighbors, when they exist.
+            if row > 0 && col > 0 {
+                mask.set_linear_index((row - 1) * n_cols + (col - 1), true);
+            }
+            if row > 0 && col < n_cols - 1 {
+                mask.set_linear_index((row - 1) * n_cols + (col + 1), true);
+            }
+            if row < n_rows - 1 && col > 0 {
+                mask.set_linear_index((row + 1) * n_cols + (col - 1), true);
+            }
+            if row < n_rows - 1 && col < n_cols - 1 {
+                mask.set_linear_index((row + 1) * n_cols + (col + 1), true);
+            }
+
+            masks.push(mask);
+        }
+
+        QueenAttacks { masks }
+    }
+
+    /// The conflict mask for the cell at linear index `idx`.
+    #[must_use]
+    pub fn mask(&self, idx: usize) -> &QueenBoard {
+        &self.masks[idx]
+    }
+
+    /// Fetch (building once and caching) the attack table for a board size.
+    #[must_use]
+    pub fn get(n_rows: usize, n_cols: usize) -> Arc<QueenAttacks> {
+        static CACHE: OnceLock<Mutex<HashMap<(usize, usize), Arc<QueenAttacks>>>> = OnceLock::new();
+        let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut cache = cache.lock().expect("QueenAttacks cache poisoned");
+        cache
+            .entry((n_rows, n_cols))
+            .or_insert_with(|| Arc::new(QueenAttacks::new(n_rows, n_cols)))
+            .clone()
+    }
 }
 
 /// Take a set of indices, and insert each into a bitset.
@@ -231,6 +419,10 @@ pub fn solve(raw_color_regions: &str, verbose: bool) -> (Option<QueenBoard>, usi
     let mut b = QueenBoard::new(n_rows, n_cols);
     let mut gidx: usize = 0;
 
+    // Precomputed per-cell conflict masks so each validation is a single
+    // intersection rather than three board scans.
+    let attacks = QueenAttacks::get(n_rows, n_cols);
+
     'outer: for queen_placement in color_region_inds.iter().multi_cartesian_product() {
         // Update the global index
         gidx += 1;
@@ -247,27 +439,12 @@ pub fn solve(raw_color_regions: &str, verbose: bool) -> (Option<QueenBoard>, usi
         // row, column, or one-away diagonals.
         for queen_idx in &queen_placement {
             // Remove this queen from the board for now so we don't accidentally
-            // count it
+            // count it against itself
             b.set_linear_index(**queen_idx, false);
 
-            // If there is a queen in one of the diagonal spots, continue
-            // to the next set of placements
-            if !b.one_off_diagonals_are_empty(**queen_idx) {
-                continue 'outer;
-            }
-
-            // Get the row and column for this spot
-            let (row, col) = b.0.row_col_of(**queen_idx);
-
-            // If there is a queen in this row, continue to the next set of
-            // placements
-            if !b.row_is_empty(row) {
-                continue 'outer;
-            }
-
-            // If there is a queen in this column, continue to the next
-            // set of placements
-            if !b.col_is_empty(col) {
+            // If this spot conflicts with any other placed queen, continue to
+            // the next set of placements
+            if b.conflicts_with(&attacks, **queen_idx) {
                 continue 'outer;
             }
 
@@ -283,6 +460,205 @@ pub fn solve(raw_color_regions: &str, verbose: bool) -> (Option<QueenBoard>, usi
     (None, gidx)
 }
 
+/// Solve the puzzle with depth-first search and constraint propagation instead
+/// of enumerating the full `multi_cartesian_product` of per-region indices,
+/// which blows up factorially. Each color region is represented by a
+/// `QueenBoard` candidate mask of its legal cells. At each step the region with
+/// the fewest remaining candidates is chosen (the minimum-remaining-values
+/// heuristic); each candidate cell is tried, and placing a queen eliminates the
+/// whole row, column, and one-off diagonal neighbors from every *other* region's
+/// mask. A fixed-point pass then forces any region that has collapsed to a
+/// single candidate (a "naked single") and backtracks as soon as any region's
+/// mask becomes empty. This is the default backend. The returned counter reports
+/// the number of search nodes visited.
+pub fn solve_cp(raw_color_regions: &str, verbose: bool) -> (Option<QueenBoard>, usize) {
+    let (color_regions, n_rows, n_cols) = parse_color_region_inds(raw_color_regions);
+
+    // One candidate mask per color region.
+    let candidates: Vec<QueenBoard> = color_regions
+        .values()
+        .map(|inds| build_queen_board_from_inds(inds, n_rows, n_cols))
+        .collect();
+
+    if verbose {
+        println!(
+            "Searching {} color regions with constraint propagation",
+            candidates.len()
+        );
+    }
+
+    let placed = vec![None; candidates.len()];
+    let mut nodes: usize = 0;
+
+    match cp_search(candidates, placed, &mut nodes) {
+        Some(solution) => {
+            let mut board = QueenBoard::new(n_rows, n_cols);
+            for idx in solution.into_iter().flatten() {
+                board.set_linear_index(idx, true);
+            }
+            (Some(board), nodes)
+        }
+        None => (None, nodes),
+    }
+}
+
+/// Place a queen for `region` at `idx`, recording it in `placed` and eliminating
+/// the conflicting cells from every other unplaced region's candidate mask.
+fn cp_place(
+    region: usize,
+    idx: usize,
+    candidates: &mut [QueenBoard],
+    placed: &mut [Option<usize>],
+) {
+    placed[region] = Some(idx);
+    for (other, mask) in candidates.iter_mut().enumerate() {
+        if other != region && placed[other].is_none() {
+            mask.eliminate_conflicts(idx);
+        }
+    }
+}
+
+/// Run the naked-single fixed point: force every region whose mask has collapsed
+/// to a single candidate, propagating each placement, until nothing changes.
+/// Returns `false` if any unplaced region's mask is empty (a dead end).
+fn cp_propagate(candidates: &mut [QueenBoard], placed: &mut [Option<usize>]) -> bool {
+    loop {
+        let mut changed = false;
+        for region in 0..candidates.len() {
+            if placed[region].is_some() {
+                continue;
+            }
+            if candidates[region].is_empty() {
+                return false;
+            }
+            if let Some(idx) = candidates[region].only_candidate() {
+                cp_place(region, idx, candidates, placed);
+                changed = true;
+            }
+        }
+        if !changed {
+            return true;
+        }
+    }
+}
+
+/// Depth-first search over the color regions with forward checking. Consumes the
+/// candidate masks and partial assignment by value so each branch gets an
+/// independent copy to backtrack over. Returns the completed assignment, or
+/// `None` if this subtree has no solution.
+fn cp_search(
+    mut candidates: Vec<QueenBoard>,
+    mut placed: Vec<Option<usize>>,
+    nodes: &mut usize,
+) -> Option<Vec<Option<usize>>> {
+    *nodes += 1;
+
+    if !cp_propagate(&mut candidates, &mut placed) {
+        return None;
+    }
+
+    // Pick the unplaced region with the fewest remaining candidates.
+    let next = (0..candidates.len())
+        .filter(|&r| placed[r].is_none())
+        .min_by_key(|&r| candidates[r].count_ones());
+
+    let Some(region) = next else {
+        // Every region is placed: this is a complete, valid solution.
+        return Some(placed);
+    };
+
+    for idx in candidates[region].get_linear_indices().collect::<Vec<_>>() {
+        let mut branch = candidates.clone();
+        let mut branch_placed = placed.clone();
+        cp_place(region, idx, &mut branch, &mut branch_placed);
+        if let Some(solution) = cp_search(branch, branch_placed, nodes) {
+            return Some(solution);
+        }
+    }
+
+    None
+}
+
+/// A lazy iterator over every distinct solution to a puzzle, produced by the
+/// same constraint-propagation search as [`solve_cp`] but driven from an
+/// explicit stack so boards are yielded as they are found rather than
+/// materialized all at once. Each solution assigns exactly one cell per color
+/// region, so no two yielded boards are equal.
+pub struct SolutionIter {
+    stack: Vec<(Vec<QueenBoard>, Vec<Option<usize>>)>,
+    n_rows: usize,
+    n_cols: usize,
+}
+
+impl Iterator for SolutionIter {
+    type Item = QueenBoard;
+
+    fn next(&mut self) -> Option<QueenBoard> {
+        while let Some((mut candidates, mut placed)) = self.stack.pop() {
+            if !cp_propagate(&mut candidates, &mut placed) {
+                continue;
+            }
+
+            let next = (0..candidates.len())
+                .filter(|&r| placed[r].is_none())
+                .min_by_key(|&r| candidates[r].count_ones());
+
+            match next {
+                None => {
+                    let mut board = QueenBoard::new(self.n_rows, self.n_cols);
+                    for idx in placed.into_iter().flatten() {
+                        board.set_linear_index(idx, true);
+                    }
+                    return Some(board);
+                }
+                Some(region) => {
+                    for idx in candidates[region].get_linear_indices().collect::<Vec<_>>() {
+                        let mut branch = candidates.clone();
+                        let mut branch_placed = placed.clone();
+                        cp_place(region, idx, &mut branch, &mut branch_placed);
+                        self.stack.push((branch, branch_placed));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Return a lazy iterator yielding each distinct solution to the puzzle.
+#[must_use]
+pub fn solve_iter(raw_color_regions: &str) -> SolutionIter {
+    let (color_regions, n_rows, n_cols) = parse_color_region_inds(raw_color_regions);
+
+    let candidates: Vec<QueenBoard> = color_regions
+        .values()
+        .map(|inds| build_queen_board_from_inds(inds, n_rows, n_cols))
+        .collect();
+    let placed = vec![None; candidates.len()];
+
+    SolutionIter {
+        stack: vec![(candidates, placed)],
+        n_rows,
+        n_cols,
+    }
+}
+
+/// Collect every distinct solution to the puzzle. A well-posed Queens puzzle has
+/// exactly one solution, so this is useful for validating hand-designed boards.
+#[must_use]
+pub fn solve_all(raw_color_regions: &str) -> Vec<QueenBoard> {
+    solve_iter(raw_color_regions).collect()
+}
+
+/// Return `true` if the puzzle has exactly one solution. Stops as soon as a
+/// second solution is found; confirming that a solution is unique still
+/// exhausts the remaining search space.
+#[must_use]
+pub fn has_unique_solution(raw_color_regions: &str) -> bool {
+    let mut solutions = solve_iter(raw_color_regions);
+    solutions.next().is_some() && solutions.next().is_none()
+}
+
 /// Print out the state of a board by placing an 'X' wherever one of the bits in the u64
 /// is set to 1, and a '.' wherever it is set to 0.
 pub fn disp_u64(board: u64) {
@@ -377,6 +753,84 @@ mod tests {
         assert!(res.0.is_some());
     }
 
+    #[test]
+    fn test_solve_all_finds_the_unique_solution() {
+        let raw_color_regions =
+            "11112333 11222344 11255346 77253344 73355334 77335344 87355333 77333333";
+        let solutions = solve_all(raw_color_regions);
+        assert_eq!(solutions.len(), 1);
+    }
+
+    #[test]
+    fn test_has_unique_solution() {
+        let raw_color_regions =
+            "11112333 11222344 11255346 77253344 73355334 77335344 87355333 77333333";
+        assert!(has_unique_solution(raw_color_regions));
+    }
+
+    #[test]
+    fn test_queen_attacks_mask_covers_row_col_and_diagonals() {
+        // Center cell of a 3x3 board attacks the whole middle row and column
+        // plus all four corners (the one-off diagonals).
+        let attacks = QueenAttacks::new(3, 3);
+        let mask = attacks.mask(4);
+        for &idx in &[1, 3, 4, 5, 7, 0, 2, 6, 8] {
+            assert!(mask.is_set(idx), "cell {idx} should be attacked");
+        }
+    }
+
+    #[test]
+    fn test_conflicts_with_detects_shared_row() {
+        let attacks = QueenAttacks::new(3, 3);
+        let board = build_queen_board_from_inds(&[0], 3, 3);
+        // Index 2 shares a row with the queen at index 0.
+        assert!(board.conflicts_with(&attacks, 2));
+        // Index 7 shares neither row, column, nor a one-off diagonal.
+        assert!(!board.conflicts_with(&attacks, 7));
+    }
+
+    #[test]
+    fn test_queen_attacks_cache_returns_same_table() {
+        let a = QueenAttacks::get(8, 8);
+        let b = QueenAttacks::get(8, 8);
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_cp_regions_are_columns() {
+        let raw_color_regions = "12345 12345 12345 12345 12345 ";
+        let res = solve_cp(raw_color_regions, false);
+        assert!(res.0.is_some());
+    }
+
+    #[test]
+    fn test_cp_regions_are_rows() {
+        let raw_color_regions = "11111 22222 33333 44444 55555";
+        let res = solve_cp(raw_color_regions, false);
+        assert!(res.0.is_some());
+    }
+
+    #[test]
+    fn test_cp_actual_board() {
+        let raw_color_regions =
+            "11112333 11222344 11255346 77253344 73355334 77335344 87355333 77333333";
+        let res = solve_cp(raw_color_regions, false);
+        assert!(res.0.is_some());
+    }
+
+    #[test]
+    fn test_cp_visits_fewer_nodes_than_brute_force() {
+        // Constraint propagation should prune the search well below the
+        // brute-force position count for the same board.
+        let raw_color_regions =
+            "11112333 11222344 11255346 77253344 73355334 77335344 87355333 77333333";
+        let (cp_board, cp_nodes) = solve_cp(raw_color_regions, false);
+        let (brute_board, brute_nodes) = solve(raw_color_regions, false);
+        assert!(cp_board.is_some());
+        assert!(brute_board.is_some());
+        assert!(cp_nodes < brute_nodes);
+    }
+
     #[rstest]
     #[case(0, &[], true, "empty board")]
     #[case(4, &[0], false, "above left occupied")]
@@ -0,0 +1,277 @@
+//! Human-style logical deduction engine.
+//!
+//! Rather than searching, this module solves (or partially solves) a puzzle
+//! using only deductions a person could make, which is useful for generating
+//! hints and difficulty ratings. A [`CellState`] grid runs parallel to the color
+//! regions, and an inference loop repeatedly applies three rules until it
+//! reaches a fixed point:
+//!
+//! 1. If a region, row, or column has exactly one `Unknown` cell left, that cell
+//!    must hold a queen.
+//! 2. Placing a queen eliminates its row, column, one-off diagonals, and the
+//!    rest of its region.
+//! 3. If every remaining `Unknown` cell of a region lies within a single row or
+//!    column, that row/column's cells can be eliminated in all other regions.
+//!
+//! The engine returns the ordered forced moves it made (each tagged with the
+//! rule that justified it) and a `solution_rate`: the fraction of cells it was
+//! able to determine. A rate of `1.0` means the puzzle is solvable by logic
+//! alone; anything less means a solver would have to guess.
+
+use crate::parse_color_region_inds;
+
+/// The deduced state of a single cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellState {
+    /// Not yet determined to hold or exclude a queen.
+    Unknown,
+    /// Determined to hold a queen.
+    Queen,
+    /// Determined not to hold a queen.
+    Eliminated,
+}
+
+/// The rule that justified a forced move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// A region had exactly one `Unknown` cell remaining.
+    LastInRegion,
+    /// A row had exactly one `Unknown` cell remaining.
+    LastInRow,
+    /// A column had exactly one `Unknown` cell remaining.
+    LastInColumn,
+}
+
+/// A single forced move: the cell a queen was placed on and why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeductionStep {
+    /// Linear index of the cell the queen was placed on.
+    pub idx: usize,
+    /// The rule that forced the placement.
+    pub rule: Rule,
+}
+
+/// The result of running the deduction engine over a puzzle.
+#[derive(Debug, Clone)]
+pub struct Deduction {
+    /// The forced moves, in the order they were deduced.
+    pub steps: Vec<DeductionStep>,
+    /// The final per-cell states, indexed by linear cell index.
+    pub states: Vec<CellState>,
+    /// Fraction of cells determined (`Queen` or `Eliminated`) in `[0.0, 1.0]`.
+    pub solution_rate: f64,
+}
+
+/// The puzzle geometry plus per-cell region membership, built once up front.
+struct Grid {
+    n_rows: usize,
+    n_cols: usize,
+    /// Cells belonging to each region.
+    regions: Vec<Vec<usize>>,
+    /// Region index of each cell.
+    region_of: Vec<usize>,
+}
+
+impl Grid {
+    fn eliminate(&self, states: &mut [CellState], idx: usize) {
+        if states[idx] == CellState::Unknown {
+            states[idx] = CellState::Eliminated;
+        }
+    }
+
+    /// Place a queen at `idx` (rule 2) and record the move.
+    fn place_queen(&self, states: &mut [CellState], steps: &mut Vec<DeductionStep>, idx: usize, rule: Rule) {
+        states[idx] = CellState::Queen;
+        steps.push(DeductionStep { idx, rule });
+
+        let row = idx / self.n_cols;
+        let col = idx % self.n_cols;
+
+        for c in 0..self.n_cols {
+            self.eliminate(states, row * self.n_cols + c);
+        }
+        for r in 0..self.n_rows {
+            self.eliminate(states, r * self.n_cols + col);
+        }
+        if row > 0 && col > 0 {
+            self.eliminate(states, (row - 1) * self.n_cols + (col - 1));
+        }
+        if row > 0 && col < self.n_cols - 1 {
+            self.eliminate(states, (row - 1) * self.n_cols + (col + 1));
+        }
+        if row < self.n_rows - 1 && col > 0 {
+            self.eliminate(states, (row + 1) * self.n_cols + (col - 1));
+        }
+        if row < self.n_rows - 1 && col < self.n_cols - 1 {
+            self.eliminate(states, (row + 1) * self.n_cols + (col + 1));
+        }
+        for &cell in &self.regions[self.region_of[idx]] {
+            self.eliminate(states, cell);
+        }
+
+        // The eliminations above also touched the queen's own cell; restore it.
+        states[idx] = CellState::Queen;
+    }
+}
+
+/// Collect the `Unknown` cells of a line, stopping early if a queen is present.
+fn unknowns_in<I: Iterator<Item = usize>>(states: &[CellState], cells: I) -> Option<Vec<usize>> {
+    let mut unknowns = Vec::new();
+    for cell in cells {
+        match states[cell] {
+            CellState::Queen => return None, // already satisfied
+            CellState::Unknown => unknowns.push(cell),
+            CellState::Eliminated => {}
+        }
+    }
+    Some(unknowns)
+}
+
+/// Solve a puzzle using only logical deduction, returning the forced moves and
+/// how much of the board could be determined without guessing.
+#[must_use]
+pub fn deduce(raw_color_regions: &str) -> Deduction {
+    let (regions_map, n_rows, n_cols) = parse_color_region_inds(raw_color_regions);
+    let regions: Vec<Vec<usize>> = regions_map.values().cloned().collect();
+
+    let mut region_of = vec![0usize; n_rows * n_cols];
+    for (region_idx, cells) in regions.iter().enumerate() {
+        for &cell in cells {
+            region_of[cell] = region_idx;
+        }
+    }
+
+    let grid = Grid {
+        n_rows,
+        n_cols,
+        regions,
+        region_of,
+    };
+
+    let mut states = vec![CellState::Unknown; n_rows * n_cols];
+    let mut steps = Vec::new();
+
+    loop {
+        let mut changed = false;
+
+        // Rule 1, regions: a region with one Unknown cell left is forced.
+        for region in &grid.regions {
+            if let Some(unknowns) = unknowns_in(&states, region.iter().copied()) {
+                if unknowns.len() == 1 {
+                    grid.place_queen(&mut states, &mut steps, unknowns[0], Rule::LastInRegion);
+                    changed = true;
+                }
+            }
+        }
+
+        // Rule 1, rows.
+        for row in 0..n_rows {
+            let cells = (0..n_cols).map(|c| row * n_cols + c);
+            if let Some(unknowns) = unknowns_in(&states, cells) {
+                if unknowns.len() == 1 {
+                    grid.place_queen(&mut states, &mut steps, unknowns[0], Rule::LastInRow);
+                    changed = true;
+                }
+            }
+        }
+
+        // Rule 1, columns.
+        for col in 0..n_cols {
+            let cells = (0..n_rows).map(|r| r * n_cols + col);
+            if let Some(unknowns) = unknowns_in(&states, cells) {
+                if unknowns.len() == 1 {
+                    grid.place_queen(&mut states, &mut steps, unknowns[0], Rule::LastInColumn);
+                    changed = true;
+                }
+            }
+        }
+
+        // Rule 3: if a region's Unknown cells all share a row (or column), that
+        // row (or column) can be eliminated from every other region.
+        for region in &grid.regions {
+            let Some(unknowns) = unknowns_in(&states, region.iter().copied()) else {
+                continue;
+            };
+            if unknowns.is_empty() {
+                continue;
+            }
+
+            let first_row = unknowns[0] / n_cols;
+            let first_col = unknowns[0] % n_cols;
+            let same_row = unknowns.iter().all(|&c| c / n_cols == first_row);
+            let same_col = unknowns.iter().all(|&c| c % n_cols == first_col);
+
+            if same_row {
+                for c in 0..n_cols {
+                    let cell = first_row * n_cols + c;
+                    if grid.region_of[cell] != grid.region_of[unknowns[0]]
+                        && states[cell] == CellState::Unknown
+                    {
+                        states[cell] = CellState::Eliminated;
+                        changed = true;
+                    }
+                }
+            }
+            if same_col {
+                for r in 0..n_rows {
+                    let cell = r * n_cols + first_col;
+                    if grid.region_of[cell] != grid.region_of[unknowns[0]]
+                        && states[cell] == CellState::Unknown
+                    {
+                        states[cell] = CellState::Eliminated;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let determined = states
+        .iter()
+        .filter(|&&s| s != CellState::Unknown)
+        .count();
+    let solution_rate = determined as f64 / states.len() as f64;
+
+    Deduction {
+        steps,
+        states,
+        solution_rate,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_cell_is_fully_determined() {
+        let result = deduce("1");
+        assert_eq!(result.steps.len(), 1);
+        assert_eq!(result.states[0], CellState::Queen);
+        assert_eq!(result.solution_rate, 1.0);
+    }
+
+    #[test]
+    fn test_deductions_place_valid_queens() {
+        let raw_color_regions =
+            "11112333 11222344 11255346 77253344 73355334 77335344 87355333 77333333";
+        let result = deduce(raw_color_regions);
+        // Every forced move should leave its cell marked as a queen, and no
+        // region can be forced more than once (at most one queen per region).
+        assert!(result.steps.len() <= 8);
+        for step in &result.steps {
+            assert_eq!(result.states[step.idx], CellState::Queen);
+        }
+    }
+
+    #[test]
+    fn test_solution_rate_is_a_fraction() {
+        let raw_color_regions = "12345 12345 12345 12345 12345 ";
+        let result = deduce(raw_color_regions);
+        assert!(result.solution_rate >= 0.0 && result.solution_rate <= 1.0);
+    }
+}
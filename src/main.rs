@@ -1,5 +1,5 @@
 use clap::Parser;
-use queens::{format_thousands, solve};
+use queens::{format_thousands, solve_cp};
 
 #[derive(Parser)]
 #[command(about = "Solve the queens problem")]
@@ -24,7 +24,7 @@ fn main() {
     let start = std::time::Instant::now();
 
     // Solve the queens problem
-    let (res, n_iters) = solve(&args.color_regions, args.verbose);
+    let (res, n_iters) = solve_cp(&args.color_regions, args.verbose);
     let run_time = start.elapsed();
     let formatted_iters = format_thousands(n_iters);
     let iter_per_second = (n_iters as f64) / run_time.as_secs_f64();